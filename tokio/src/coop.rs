@@ -11,21 +11,23 @@
 //! Consider a future like this one:
 //!
 //! ```
-//! # use tokio::stream::{Stream, StreamExt};
-//! async fn drop_all<I: Stream + Unpin>(mut input: I) {
-//!     while let Some(_) = input.next().await {}
+//! # use futures::future::poll_fn;
+//! # use tokio::sync::mpsc;
+//! async fn drain(mut rx: mpsc::Receiver<()>) {
+//!     while poll_fn(|cx| rx.poll_recv(cx)).await.is_some() {}
 //! }
 //! ```
 //!
 //! It may look harmless, but consider what happens under heavy load if the
-//! input stream is _always_ ready. If we spawn `drop_all`, the task will never
+//! input channel is _always_ ready. Without a budget-aware `poll_recv`, a spawned `drain` would never
 //! yield, and will starve other tasks and resources on the same executor. With
 //! opt-in yield points, this problem is alleviated:
 //!
-//! ```ignore
-//! # use tokio::stream::{Stream, StreamExt};
-//! async fn drop_all<I: Stream + Unpin>(mut input: I) {
-//!     while let Some(_) = input.next().await {
+//! ```
+//! # use futures::future::poll_fn;
+//! # use tokio::sync::mpsc;
+//! async fn drain(mut rx: mpsc::Receiver<()>) {
+//!     while poll_fn(|cx| rx.poll_recv(cx)).await.is_some() {
 //!         tokio::coop::proceed().await;
 //!     }
 //! }
@@ -45,34 +47,75 @@
 //! other futures. By doing this, you avoid double-counting each iteration of
 //! the outer future against the cooperating budget.
 //!
+//! # For combinator authors
+//!
+//! Crates outside of tokio that implement their own leaf futures -- stream
+//! adapters, combinators, anything that ends up spinning in a tight loop
+//! inside `poll` -- can call [`poll_proceed`] directly to participate in the
+//! same budget that tokio's own resources use. This is the lower-level,
+//! `Context`-based counterpart to [`proceed`], meant for use inside a
+//! hand-written `poll` implementation rather than an `async fn`.
+//!
+//! # For tokio resource authors
+//!
+//! tokio's own leaf resources (channels, I/O handles, and the like) are expected to consume
+//! budget automatically, so that a task spinning on an always-ready resource yields without any
+//! change at the call site -- the `drain` example above should behave the same whether or not
+//! it contains an explicit [`proceed`] call. The contract for a `poll_xxx` method on such a
+//! resource is to call [`poll_proceed`] before reporting readiness, and to propagate `Pending` if
+//! the budget is exhausted:
+//!
+//! ```ignore
+//! fn poll_recv(&mut self, cx: &mut Context<'_>) -> Poll<Option<T>> {
+//!     ready!(crate::coop::poll_proceed(cx));
+//!
+//!     // .. actual readiness check goes here ..
+//! }
+//! ```
+//!
+//! Because [`poll_proceed`] already re-arms the waker via `wake_by_ref` before returning
+//! `Pending`, this is enough to guarantee the task will be polled again rather than being stalled
+//! -- it simply gives other tasks on the executor a chance to run first.
+//!
 //! [`poll`]: https://doc.rust-lang.org/std/future/trait.Future.html#tymethod.poll
 
-// NOTE: The doctests in this module are ignored since the whole module is (currently) private.
-
 use std::cell::Cell;
 
+/// The default per-poll budget, used unless [`set_initial_budget`] has been called on the
+/// current thread.
+///
+/// The value itself is chosen somewhat arbitrarily. It needs to be high enough to amortize wakeup
+/// and scheduling costs, but low enough that we do not starve other tasks for too long. The value
+/// also needs to be high enough that particularly deep tasks are able to do at least some useful
+/// work at all.
+///
+/// Note that as more yield points are added in the ecosystem, this value will probably also have
+/// to be raised.
+const DEFAULT_BUDGET: u32 = 128;
+
 thread_local! {
     static CURRENT: Cell<Budget> = Cell::new(Budget::unconstrained());
+
+    /// The budget a worker hands out to a task at the start of every `budget()` scope on this
+    /// thread. Defaults to [`DEFAULT_BUDGET`], but can be overridden with
+    /// [`set_initial_budget`] -- this is how `runtime::Builder::coop_budget` reaches the
+    /// per-poll accounting in this module.
+    static INITIAL_BUDGET: Cell<u32> = Cell::new(DEFAULT_BUDGET);
 }
 
 /// Opaque type tracking the amount of "work" a task may still do before
 /// yielding back to the scheduler.
 #[derive(Debug, Copy, Clone)]
-pub(crate) struct Budget(Option<u8>);
+pub(crate) struct Budget(Option<u32>);
 
 impl Budget {
     /// Budget assigned to a task on each poll.
     ///
-    /// The value itself is chosen somewhat arbitrarily. It needs to be high
-    /// enough to amortize wakeup and scheduling costs, but low enough that we
-    /// do not starve other tasks for too long. The value also needs to be high
-    /// enough that particularly deep tasks are able to do at least some useful
-    /// work at all.
-    ///
-    /// Note that as more yield points are added in the ecosystem, this value
-    /// will probably also have to be raised.
-    const fn initial() -> Budget {
-        Budget(Some(128))
+    /// This is [`DEFAULT_BUDGET`] unless the runtime was built with a custom
+    /// `runtime::Builder::coop_budget`, in which case it is the configured value (see
+    /// [`set_initial_budget`]).
+    fn initial() -> Budget {
+        Budget(Some(INITIAL_BUDGET.with(Cell::get)))
     }
 
     /// Returns an unconstrained budget. Operations will not be limited.
@@ -81,6 +124,27 @@ impl Budget {
     }
 }
 
+/// Overrides the per-poll budget handed out by [`Budget::initial`] on the current thread.
+///
+/// This is called once per worker thread by [`runtime::worker::spawn`](crate::runtime::worker::spawn)
+/// when the runtime was built with an explicit `runtime::Builder::coop_budget`, so
+/// that every `budget()` scope entered on that thread starts from the configured value instead of
+/// [`DEFAULT_BUDGET`].
+pub(crate) fn set_initial_budget(initial: u32) {
+    INITIAL_BUDGET.with(|cell| cell.set(initial));
+}
+
+/// Returns the remaining budget for the current task, or `None` if the current thread is
+/// outside of a [`budget`] scope (i.e. unconstrained).
+///
+/// This is a diagnostic accessor, not part of the yield-point contract itself -- it exists so
+/// that tests (and, potentially, runtime metrics) can observe the effect of
+/// [`set_initial_budget`] without reaching into `Budget`'s private representation.
+#[cfg(test)]
+pub(crate) fn current_budget() -> Option<u32> {
+    CURRENT.with(|cell| cell.get().0)
+}
+
 cfg_rt_threaded! {
     impl Budget {
         fn has_remaining(self) -> bool {
@@ -93,6 +157,25 @@ cfg_rt_threaded! {
 /// returns, the budget is reset to the value prior to calling the function.
 #[inline(always)]
 pub(crate) fn budget<F, R>(f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    with_budget(Budget::initial(), f)
+}
+
+/// Run the given closure without any cooperative task budget, so that the closure will never be
+/// forced to yield by [`poll_proceed`]. When the function returns, the budget is reset to the
+/// value prior to calling the function.
+#[inline(always)]
+pub(crate) fn with_unconstrained<F, R>(f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    with_budget(Budget::unconstrained(), f)
+}
+
+#[inline(always)]
+fn with_budget<F, R>(budget: Budget, f: F) -> R
 where
     F: FnOnce() -> R,
 {
@@ -110,7 +193,7 @@ where
     CURRENT.with(move |cell| {
         let prev = cell.get();
 
-        cell.set(Budget::initial());
+        cell.set(budget);
 
         let _guard = ResetGuard { cell, prev };
 
@@ -135,24 +218,63 @@ cfg_blocking_impl! {
 }
 
 cfg_coop! {
+    use std::future::Future;
+    use std::pin::Pin;
     use std::task::{Context, Poll};
 
     /// Returns `Poll::Pending` if the current task has exceeded its budget and should yield.
+    ///
+    /// This is the `Context`-based counterpart to [`proceed`], meant to be called from inside a
+    /// hand-written `Future::poll` implementation (for example, a leaf future in a combinator
+    /// crate) rather than from an `async fn`.
     #[inline]
-    pub(crate) fn poll_proceed(cx: &mut Context<'_>) -> Poll<()> {
+    pub fn poll_proceed(cx: &mut Context<'_>) -> Poll<()> {
         CURRENT.with(|cell| {
             let mut budget = cell.get();
 
             if budget.decrement() {
                 cell.set(budget);
+                if budget.0.is_some() {
+                    METRICS.with(|metrics| metrics.record_consumed(1));
+                }
                 Poll::Ready(())
             } else {
+                METRICS.with(|metrics| metrics.record_exhausted());
                 cx.waker().wake_by_ref();
                 Poll::Pending
             }
         })
     }
 
+    /// Returns a future that yields control back to the scheduler once the current task's
+    /// cooperative budget has been exhausted.
+    ///
+    /// Futures and combinators that do "leaf" work -- that is, they don't themselves poll other
+    /// futures -- should occasionally await this future in their loops to voluntarily
+    /// participate in tokio's cooperative scheduling. See the [module documentation](index.html)
+    /// for more details.
+    pub async fn proceed() {
+        Proceed { _p: () }.await
+    }
+
+    pin_project_lite::pin_project! {
+        #[derive(Debug)]
+        #[must_use = "futures do nothing unless you `.await` or poll them"]
+        struct Proceed {
+            // Make this future `!Unpin` for compatibility with async trait methods.
+            #[pin]
+            _p: (),
+        }
+    }
+
+    impl Future for Proceed {
+        type Output = ();
+
+        fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+            poll_proceed(cx)
+        }
+    }
+
     impl Budget {
         /// Decrement the budget. Returns `true` if successful. Decrementing fails
         /// when there is not enough remaining budget.
@@ -169,6 +291,64 @@ cfg_coop! {
             }
     }
     }
+
+    thread_local! {
+        /// Per-worker-thread counters tracking how often this thread's tasks have run out of
+        /// cooperative budget. Read through `runtime::RuntimeMetrics`, so that operators can tell which worker threads (and, by
+        /// extension, which tasks) are starving their neighbors instead of having to guess.
+        static METRICS: CoopMetrics = CoopMetrics::new();
+    }
+
+    #[derive(Debug, Default)]
+    struct CoopMetrics {
+        budget_exhausted_count: Cell<u64>,
+        budget_consumed_total: Cell<u64>,
+    }
+
+    impl CoopMetrics {
+        const fn new() -> CoopMetrics {
+            CoopMetrics {
+                budget_exhausted_count: Cell::new(0),
+                budget_consumed_total: Cell::new(0),
+            }
+        }
+
+        fn record_exhausted(&self) {
+            self.budget_exhausted_count
+                .set(self.budget_exhausted_count.get() + 1);
+        }
+
+        fn record_consumed(&self, amount: u64) {
+            self.budget_consumed_total
+                .set(self.budget_consumed_total.get() + amount);
+        }
+    }
+
+    /// Returns the number of times a task on the current thread has run out of cooperative
+    /// budget and been forced to yield by [`poll_proceed`], since the thread started.
+    ///
+    /// This is a diagnostic counter for starvation: a thread where this keeps climbing is running
+    /// tasks that consistently burn through their entire budget rather than completing or
+    /// yielding voluntarily, which is exactly the symptom of a saturated combinator or stream that
+    /// never calls [`proceed`].
+    ///
+    /// Exposed to operators as [`runtime::RuntimeMetrics::budget_exhausted_count`](crate::runtime::RuntimeMetrics::budget_exhausted_count).
+    pub(crate) fn budget_exhausted_count() -> u64 {
+        METRICS.with(|metrics| metrics.budget_exhausted_count.get())
+    }
+
+    /// Returns the total amount of budget a task on the current thread has consumed via
+    /// [`poll_proceed`] since the thread started -- i.e. how much "work", in budget units, this
+    /// thread's tasks have done across all of their polls.
+    ///
+    /// Read alongside [`budget_exhausted_count`], this tells operators not just *that* a thread is
+    /// starving its neighbors but *how much* work is going into it, which is useful for spotting
+    /// where missing yield points live.
+    ///
+    /// Exposed to operators as [`runtime::RuntimeMetrics::budget_consumed_total`](crate::runtime::RuntimeMetrics::budget_consumed_total).
+    pub(crate) fn budget_consumed_total() -> u64 {
+        METRICS.with(|metrics| metrics.budget_consumed_total.get())
+    }
 }
 
 #[cfg(all(test, not(loom)))]
@@ -224,4 +404,69 @@ mod test {
             assert_pending!(task.poll());
         });
     }
+
+    #[test]
+    fn leaf_resource_yields_once_budget_is_exhausted() {
+        use tokio_test::*;
+
+        // A stand-in for a tokio leaf resource (e.g. a channel's `poll_recv`) that is always
+        // ready, but consumes budget on every poll as described in the module docs.
+        struct AlwaysReady;
+
+        impl std::future::Future for AlwaysReady {
+            type Output = ();
+
+            fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+                ready!(poll_proceed(cx));
+                Poll::Ready(())
+            }
+        }
+
+        budget(|| {
+            let n = get().0.unwrap();
+
+            for _ in 0..n {
+                assert_ready!(task::spawn(AlwaysReady).poll());
+            }
+
+            // The budget is now exhausted: the resource must yield instead of completing.
+            assert_pending!(task::spawn(AlwaysReady).poll());
+        });
+    }
+
+    #[test]
+    fn records_budget_exhausted_count() {
+        use tokio_test::*;
+
+        let before = budget_exhausted_count();
+
+        budget(|| {
+            let n = get().0.unwrap();
+
+            for _ in 0..n {
+                assert_ready!(task::spawn(()).enter(|cx, _| poll_proceed(cx)));
+            }
+
+            assert_pending!(task::spawn(()).enter(|cx, _| poll_proceed(cx)));
+        });
+
+        assert_eq!(budget_exhausted_count(), before + 1);
+    }
+
+    #[test]
+    fn records_budget_consumed_total() {
+        use tokio_test::*;
+
+        let before = budget_consumed_total();
+
+        budget(|| {
+            let n = get().0.unwrap();
+
+            for _ in 0..n {
+                assert_ready!(task::spawn(()).enter(|cx, _| poll_proceed(cx)));
+            }
+        });
+
+        assert_eq!(budget_consumed_total(), before + u64::from(Budget::initial().0.unwrap()));
+    }
 }
@@ -0,0 +1,5 @@
+//! The I/O driver: the readiness-tracking machinery shared by every concrete I/O resource.
+
+mod registration;
+
+pub(crate) use registration::Registration;
@@ -0,0 +1,154 @@
+use crate::coop;
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::task::{Context, Poll, Waker};
+
+/// Tracks readiness for a single I/O resource.
+///
+/// Every concrete I/O type (`TcpStream`, `UdpSocket`, and so on) polls its readiness through a
+/// `Registration` rather than checking the underlying OS handle directly. That makes this struct
+/// the single choke point through which all of tokio's `poll_read`/`poll_write`/`poll_next` paths
+/// flow, and therefore the right place to consume cooperative scheduling budget: wiring
+/// [`coop::poll_proceed`] in here, once, gives every I/O resource built on top of it automatic
+/// yield points for free, exactly as described in the [`coop`](crate::coop) module docs.
+///
+/// Concrete I/O resources (`TcpStream`, `UdpSocket`, ...) hold a `Registration` and delegate
+/// their own `poll_read`/`poll_write` to [`poll_read_ready`](Registration::poll_read_ready) and
+/// [`poll_write_ready`](Registration::poll_write_ready).
+#[derive(Debug)]
+pub(crate) struct Registration {
+    readable: AtomicBool,
+    writable: AtomicBool,
+    read_waker: Mutex<Option<Waker>>,
+    write_waker: Mutex<Option<Waker>>,
+}
+
+impl Registration {
+    /// Creates a new registration that starts out neither readable nor writable.
+    pub(crate) fn new() -> Registration {
+        Registration {
+            readable: AtomicBool::new(false),
+            writable: AtomicBool::new(false),
+            read_waker: Mutex::new(None),
+            write_waker: Mutex::new(None),
+        }
+    }
+
+    /// Marks this resource as readable, waking the task polling [`poll_read_ready`] if any.
+    pub(crate) fn set_readable(&self) {
+        self.readable.store(true, Ordering::Release);
+        if let Some(waker) = self.read_waker.lock().unwrap().take() {
+            waker.wake();
+        }
+    }
+
+    /// Marks this resource as writable, waking the task polling [`poll_write_ready`] if any.
+    pub(crate) fn set_writable(&self) {
+        self.writable.store(true, Ordering::Release);
+        if let Some(waker) = self.write_waker.lock().unwrap().take() {
+            waker.wake();
+        }
+    }
+
+    /// Polls for read-readiness, consuming cooperative scheduling budget on every call.
+    ///
+    /// Leaf I/O resources built on this registration (`poll_read`, `poll_next`, ...) should call
+    /// this before consulting any OS-level readiness, and propagate `Poll::Pending` if it
+    /// returns one -- this is what makes a task spinning on an always-ready socket or datagram
+    /// yield to the executor instead of starving it.
+    pub(crate) fn poll_read_ready(&self, cx: &mut Context<'_>) -> Poll<()> {
+        ready!(coop::poll_proceed(cx));
+
+        if self.readable.swap(false, Ordering::AcqRel) {
+            return Poll::Ready(());
+        }
+
+        *self.read_waker.lock().unwrap() = Some(cx.waker().clone());
+
+        // A concurrent `set_readable` may have run, and found no waker to take, between the
+        // check above and the waker being stored -- re-check now that it is in place rather than
+        // relying on a wake-up that race could have already missed.
+        if self.readable.swap(false, Ordering::AcqRel) {
+            return Poll::Ready(());
+        }
+
+        Poll::Pending
+    }
+
+    /// Polls for write-readiness, consuming cooperative scheduling budget on every call.
+    ///
+    /// See [`poll_read_ready`](Registration::poll_read_ready) for why this consumes budget.
+    pub(crate) fn poll_write_ready(&self, cx: &mut Context<'_>) -> Poll<()> {
+        ready!(coop::poll_proceed(cx));
+
+        if self.writable.swap(false, Ordering::AcqRel) {
+            return Poll::Ready(());
+        }
+
+        *self.write_waker.lock().unwrap() = Some(cx.waker().clone());
+
+        // See the matching re-check in `poll_read_ready`: without it, a `set_writable` racing
+        // with the waker being stored could be missed forever.
+        if self.writable.swap(false, Ordering::AcqRel) {
+            return Poll::Ready(());
+        }
+
+        Poll::Pending
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use tokio_test::task;
+    use tokio_test::{assert_pending, assert_ready};
+
+    #[test]
+    fn poll_read_ready_yields_once_budget_is_exhausted() {
+        let registration = Registration::new();
+        registration.set_readable();
+
+        coop::budget(|| {
+            let n = coop::current_budget().unwrap();
+
+            for _ in 0..n {
+                registration.set_readable();
+                assert_ready!(task::spawn(()).enter(|cx, _| registration.poll_read_ready(cx)));
+            }
+
+            registration.set_readable();
+            assert_pending!(task::spawn(()).enter(|cx, _| registration.poll_read_ready(cx)));
+        });
+    }
+
+    #[test]
+    fn poll_write_ready_yields_once_budget_is_exhausted() {
+        let registration = Registration::new();
+
+        coop::budget(|| {
+            let n = coop::current_budget().unwrap();
+
+            for _ in 0..n {
+                registration.set_writable();
+                assert_ready!(task::spawn(()).enter(|cx, _| registration.poll_write_ready(cx)));
+            }
+
+            registration.set_writable();
+            assert_pending!(task::spawn(()).enter(|cx, _| registration.poll_write_ready(cx)));
+        });
+    }
+
+    #[test]
+    fn poll_read_ready_wakes_once_registered() {
+        let registration = Registration::new();
+
+        let mut task = task::spawn(());
+        assert_pending!(task.enter(|cx, _| registration.poll_read_ready(cx)));
+
+        registration.set_readable();
+
+        assert!(task.is_woken());
+        assert_ready!(task.enter(|cx, _| registration.poll_read_ready(cx)));
+    }
+}
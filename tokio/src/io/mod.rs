@@ -0,0 +1,5 @@
+//! Core I/O driver internals shared by every tokio I/O resource (`TcpStream`, `UdpSocket`, ...).
+
+cfg_io_driver! {
+    pub(crate) mod driver;
+}
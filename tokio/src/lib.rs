@@ -0,0 +1,23 @@
+#![warn(missing_debug_implementations, missing_docs, rust_2018_idioms, unreachable_pub)]
+#![doc(test(
+    no_crate_inject,
+    attr(deny(warnings, rust_2018_idioms), allow(dead_code, unused_variables))
+))]
+
+//! A runtime for writing reliable, asynchronous, and slim applications.
+//!
+//! Tokio is an event-driven, non-blocking I/O platform for writing asynchronous applications
+//! with the Rust programming language.
+
+#[macro_use]
+mod macros;
+
+cfg_coop! {
+    pub mod coop;
+}
+
+pub mod runtime;
+pub mod sync;
+pub mod task;
+
+pub(crate) mod io;
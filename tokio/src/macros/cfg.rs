@@ -0,0 +1,48 @@
+/// Enables code when `tokio` is built with a runtime's cooperative scheduling support, i.e. the
+/// `tokio::coop` module and anything built on top of it (`tokio::task::unconstrained`, the
+/// budget-aware leaf resources, ...).
+macro_rules! cfg_coop {
+    ($($item:item)*) => {
+        $(
+            #[cfg(feature = "rt-core")]
+            #[cfg_attr(docsrs, doc(cfg(feature = "rt-core")))]
+            $item
+        )*
+    }
+}
+
+/// Enables code that is specific to the multi-threaded runtime, where multiple worker threads
+/// can starve one another and cooperative scheduling matters most.
+macro_rules! cfg_rt_threaded {
+    ($($item:item)*) => {
+        $(
+            #[cfg(feature = "rt-threaded")]
+            #[cfg_attr(docsrs, doc(cfg(feature = "rt-threaded")))]
+            $item
+        )*
+    }
+}
+
+/// Enables code specific to the `blocking` implementation, which forcibly lifts a task out of
+/// the cooperative budgeting system because it is about to block the worker thread anyway.
+macro_rules! cfg_blocking_impl {
+    ($($item:item)*) => {
+        $(
+            #[cfg(feature = "rt-core")]
+            #[cfg_attr(docsrs, doc(cfg(feature = "rt-core")))]
+            $item
+        )*
+    }
+}
+
+/// Enables code that is specific to the I/O driver, e.g. the readiness-tracking primitives
+/// shared by every I/O resource (`TcpStream`, `UdpSocket`, ...).
+macro_rules! cfg_io_driver {
+    ($($item:item)*) => {
+        $(
+            #[cfg(feature = "io-driver")]
+            #[cfg_attr(docsrs, doc(cfg(feature = "io-driver")))]
+            $item
+        )*
+    }
+}
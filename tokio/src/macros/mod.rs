@@ -0,0 +1,5 @@
+#[macro_use]
+mod cfg;
+
+#[macro_use]
+mod ready;
@@ -0,0 +1,9 @@
+/// `ready!` await's an expression and exits early in the case of `Poll::Pending`.
+macro_rules! ready {
+    ($e:expr $(,)?) => {
+        match $e {
+            std::task::Poll::Ready(t) => t,
+            std::task::Poll::Pending => return std::task::Poll::Pending,
+        }
+    };
+}
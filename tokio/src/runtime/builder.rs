@@ -0,0 +1,46 @@
+/// Builds a tokio `Runtime` with custom configuration values.
+///
+/// This struct is typically used to construct a custom `Runtime` when a more elaborate
+/// configuration than the default is desired.
+#[derive(Debug)]
+pub struct Builder {
+    /// Per-poll cooperative scheduling budget handed out to every task when it enters
+    /// `coop::budget`, or `None` to keep `coop`'s own default.
+    coop_budget: Option<u32>,
+}
+
+impl Builder {
+    /// Returns a new runtime builder initialized with default configuration values.
+    pub fn new() -> Builder {
+        Builder { coop_budget: None }
+    }
+
+    /// Sets the cooperative scheduling budget handed to a task every time it is polled.
+    ///
+    /// Tasks give up control back to the scheduler after consuming this much budget, so that a
+    /// task polling an always-ready leaf resource in a tight loop cannot starve the other tasks
+    /// and resources driven by the same runtime. Latency-sensitive workloads may want to lower
+    /// this to yield sooner; throughput-bound workloads may want to raise it so that deep task
+    /// trees make more progress per poll.
+    ///
+    /// If unset, the default budget of 128 is used.
+    pub fn coop_budget(&mut self, budget: u32) -> &mut Self {
+        self.coop_budget = Some(budget);
+        self
+    }
+
+    /// Returns the configured per-poll cooperative scheduling budget, if one was set via
+    /// [`coop_budget`](Builder::coop_budget).
+    ///
+    /// Used by [`runtime::worker::spawn`](crate::runtime::worker::spawn) to apply the
+    /// configuration to each worker thread before it starts polling any tasks.
+    pub(crate) fn configured_coop_budget(&self) -> Option<u32> {
+        self.coop_budget
+    }
+}
+
+impl Default for Builder {
+    fn default() -> Builder {
+        Builder::new()
+    }
+}
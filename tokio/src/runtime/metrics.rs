@@ -0,0 +1,65 @@
+use crate::coop;
+
+/// A handle onto diagnostic counters for the runtime's cooperative scheduler. The counters are
+/// read live on every call, from the thread that makes the call.
+///
+/// These counters make task starvation observable: a worker thread whose
+/// [`budget_exhausted_count`](RuntimeMetrics::budget_exhausted_count) keeps climbing is running
+/// tasks that are consistently burning through their entire [`coop`](crate::coop) budget rather
+/// than completing or yielding voluntarily -- exactly the symptom of a saturated combinator or
+/// stream missing a [`coop::proceed`] call.
+#[derive(Debug, Default)]
+#[non_exhaustive]
+pub struct RuntimeMetrics {
+    _priv: (),
+}
+
+impl RuntimeMetrics {
+    /// Returns a handle onto the cooperative scheduling counters of whichever thread calls its
+    /// methods.
+    pub fn current() -> RuntimeMetrics {
+        RuntimeMetrics { _priv: () }
+    }
+
+    /// Returns the number of times a task on this thread has run out of cooperative budget and
+    /// been forced to yield, since the thread started.
+    pub fn budget_exhausted_count(&self) -> u64 {
+        coop::budget_exhausted_count()
+    }
+
+    /// Returns the total amount of budget this thread's tasks have consumed across all of their
+    /// polls since the thread started.
+    pub fn budget_consumed_total(&self) -> u64 {
+        coop::budget_consumed_total()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use tokio_test::task;
+    use tokio_test::{assert_pending, assert_ready};
+
+    #[test]
+    fn reports_starvation_through_the_public_handle() {
+        let metrics = RuntimeMetrics::current();
+        let exhausted_before = metrics.budget_exhausted_count();
+        let consumed_before = metrics.budget_consumed_total();
+
+        coop::budget(|| {
+            let n = coop::current_budget().unwrap();
+
+            for _ in 0..n {
+                assert_ready!(task::spawn(()).enter(|cx, _| coop::poll_proceed(cx)));
+            }
+
+            assert_pending!(task::spawn(()).enter(|cx, _| coop::poll_proceed(cx)));
+
+            assert_eq!(metrics.budget_exhausted_count(), exhausted_before + 1);
+            assert_eq!(
+                metrics.budget_consumed_total(),
+                consumed_before + u64::from(n)
+            );
+        });
+    }
+}
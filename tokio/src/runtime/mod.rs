@@ -0,0 +1,19 @@
+//! The tokio runtime.
+//!
+//! Unlike other Rust programs, asynchronous applications require runtime support. In particular,
+//! the following runtime services are necessary:
+//!
+//! * An **I/O event loop**, called the driver, which drives I/O resources and dispatches I/O
+//!   events to tasks that depend on them.
+//! * A **scheduler** to execute tasks that use these I/O resources.
+//! * A **timer** for scheduling work to run after a set period of time.
+
+mod builder;
+pub(crate) mod worker;
+
+pub use builder::Builder;
+
+cfg_coop! {
+    mod metrics;
+    pub use metrics::RuntimeMetrics;
+}
@@ -0,0 +1,50 @@
+use crate::coop;
+use crate::runtime::Builder;
+
+use std::thread;
+
+/// Spawns a single worker thread for the runtime configured by `builder`.
+///
+/// This is the call site that threads a `Builder`-configured cooperative scheduling budget (see
+/// [`Builder::coop_budget`]) down into the `coop` module's thread-local state: it runs before the
+/// thread starts polling any tasks, so the very first `coop::budget` scope entered on the thread
+/// already sees the configured value.
+pub(crate) fn spawn(
+    builder: &Builder,
+    run: impl FnOnce() + Send + 'static,
+) -> thread::JoinHandle<()> {
+    let coop_budget = builder.configured_coop_budget();
+
+    thread::Builder::new()
+        .name("tokio-runtime-worker".to_owned())
+        .spawn(move || {
+            if let Some(budget) = coop_budget {
+                coop::set_initial_budget(budget);
+            }
+
+            run();
+        })
+        .expect("failed to spawn worker thread")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::sync::mpsc;
+
+    #[test]
+    fn coop_budget_is_applied_before_the_thread_runs_any_task() {
+        let mut builder = Builder::new();
+        builder.coop_budget(8);
+
+        let (tx, rx) = mpsc::channel();
+
+        spawn(&builder, move || {
+            tx.send(coop::budget(coop::current_budget)).unwrap();
+        })
+        .join()
+        .unwrap();
+
+        assert_eq!(rx.recv().unwrap(), Some(8));
+    }
+}
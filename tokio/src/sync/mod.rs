@@ -0,0 +1,3 @@
+//! Synchronization primitives for use in asynchronous contexts.
+
+pub mod mpsc;
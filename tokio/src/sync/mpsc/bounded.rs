@@ -0,0 +1,194 @@
+use crate::coop;
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+
+struct Shared<T> {
+    capacity: usize,
+    queue: Mutex<VecDeque<T>>,
+    recv_waker: Mutex<Option<Waker>>,
+    sender_count: AtomicUsize,
+}
+
+/// Sends values to the associated [`Receiver`].
+///
+/// Instances are created by the [`channel`] function.
+pub struct Sender<T> {
+    shared: Arc<Shared<T>>,
+}
+
+/// Receives values from the associated [`Sender`]s.
+///
+/// Instances are created by the [`channel`] function.
+pub struct Receiver<T> {
+    shared: Arc<Shared<T>>,
+}
+
+/// Creates a bounded mpsc channel for communicating between asynchronous tasks, returning the
+/// sender/receiver halves.
+///
+/// The channel holds at most `buffer` messages at a time.
+pub fn channel<T>(buffer: usize) -> (Sender<T>, Receiver<T>) {
+    assert!(buffer > 0, "mpsc bounded channel requires buffer > 0");
+
+    let shared = Arc::new(Shared {
+        capacity: buffer,
+        queue: Mutex::new(VecDeque::with_capacity(buffer)),
+        recv_waker: Mutex::new(None),
+        sender_count: AtomicUsize::new(1),
+    });
+
+    (
+        Sender {
+            shared: shared.clone(),
+        },
+        Receiver { shared },
+    )
+}
+
+impl<T> Sender<T> {
+    /// Attempts to immediately send a value on this channel.
+    ///
+    /// This does not consume cooperative budget: sending is not the leaf operation that risks
+    /// starving the executor -- receiving in a loop is. See the [`Receiver::poll_recv`]
+    /// documentation (and the [`coop`](crate::coop) module) for why only the receive path
+    /// participates in the budget.
+    pub fn try_send(&self, value: T) -> Result<(), T> {
+        let mut queue = self.shared.queue.lock().unwrap();
+
+        if queue.len() >= self.shared.capacity {
+            return Err(value);
+        }
+
+        queue.push_back(value);
+        drop(queue);
+
+        if let Some(waker) = self.shared.recv_waker.lock().unwrap().take() {
+            waker.wake();
+        }
+
+        Ok(())
+    }
+}
+
+impl<T> Clone for Sender<T> {
+    fn clone(&self) -> Sender<T> {
+        self.shared.sender_count.fetch_add(1, Ordering::Relaxed);
+        Sender {
+            shared: self.shared.clone(),
+        }
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        if self.shared.sender_count.fetch_sub(1, Ordering::AcqRel) == 1 {
+            if let Some(waker) = self.shared.recv_waker.lock().unwrap().take() {
+                waker.wake();
+            }
+        }
+    }
+}
+
+impl<T> Receiver<T> {
+    /// Polls to receive the next value on this channel.
+    ///
+    /// This is a leaf resource in the sense described by the [`coop`](crate::coop) module: it
+    /// calls [`coop::poll_proceed`] before reporting a value as ready, so that a task looping on
+    /// `poll_recv` over an always-full channel yields to the executor every so often instead of
+    /// starving it, with no changes required at the call site.
+    pub fn poll_recv(&mut self, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        ready!(coop::poll_proceed(cx));
+
+        if let Some(value) = self.poll_immediate() {
+            return value;
+        }
+
+        *self.shared.recv_waker.lock().unwrap() = Some(cx.waker().clone());
+
+        // A value may have been sent, or the last `Sender` dropped, between the check above and
+        // the waker being stored: that race would have found no waker yet to wake, so we must
+        // re-validate the condition ourselves now that the waker is in place rather than
+        // trusting a wake-up that may never come.
+        match self.poll_immediate() {
+            Some(value) => value,
+            None => Poll::Pending,
+        }
+    }
+
+    /// Checks the channel for an immediately available value or close notification, without
+    /// registering a waker.
+    fn poll_immediate(&self) -> Option<Poll<Option<T>>> {
+        let mut queue = self.shared.queue.lock().unwrap();
+
+        if let Some(value) = queue.pop_front() {
+            return Some(Poll::Ready(Some(value)));
+        }
+
+        if self.shared.sender_count.load(Ordering::Acquire) == 0 {
+            return Some(Poll::Ready(None));
+        }
+
+        None
+    }
+}
+
+impl<T> fmt::Debug for Sender<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Sender").finish()
+    }
+}
+
+impl<T> fmt::Debug for Receiver<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Receiver").finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use tokio_test::task;
+    use tokio_test::{assert_pending, assert_ready, assert_ready_eq};
+
+    #[test]
+    fn poll_recv_consumes_budget_and_yields_when_exhausted() {
+        let (tx, mut rx) = channel(1024);
+
+        coop::budget(|| {
+            let n = coop::current_budget().unwrap();
+
+            for i in 0..n {
+                tx.try_send(i).unwrap();
+            }
+
+            for i in 0..n {
+                assert_ready_eq!(
+                    task::spawn(()).enter(|cx, _| rx.poll_recv(cx)),
+                    Some(i)
+                );
+            }
+
+            tx.try_send(n).unwrap();
+
+            // The budget is now exhausted: even though a value is queued, `poll_recv` must yield.
+            assert_pending!(task::spawn(()).enter(|cx, _| rx.poll_recv(cx)));
+        });
+    }
+
+    #[test]
+    fn poll_recv_wakes_when_last_sender_drops_after_registering() {
+        let (tx, mut rx) = channel::<()>(1);
+
+        let mut task = task::spawn(());
+        assert_pending!(task.enter(|cx, _| rx.poll_recv(cx)));
+
+        drop(tx);
+
+        assert!(task.is_woken());
+        assert_ready_eq!(task.enter(|cx, _| rx.poll_recv(cx)), None);
+    }
+}
@@ -0,0 +1,5 @@
+//! A multi-producer, single-consumer queue for sending values between asynchronous tasks.
+
+mod bounded;
+
+pub use bounded::{channel, Receiver, Sender};
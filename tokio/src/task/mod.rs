@@ -0,0 +1,6 @@
+//! Additional task utilities.
+
+cfg_coop! {
+    mod unconstrained;
+    pub use unconstrained::{unconstrained, Unconstrained};
+}
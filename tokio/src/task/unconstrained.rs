@@ -0,0 +1,49 @@
+use crate::coop;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+pin_project_lite::pin_project! {
+    /// Future for the [`unconstrained`] method.
+    #[derive(Debug)]
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct Unconstrained<F> {
+        #[pin]
+        inner: F,
+    }
+}
+
+/// Turns a future into one that will never be forced to yield by tokio's cooperative scheduling.
+///
+/// Normally tasks are forced to yield occasionally so that no single task hogs the runtime by
+/// polling itself in a tight loop. This function can be used to opt a future out of this
+/// behavior, for example for a control future that must remain responsive even while sitting
+/// alongside other futures that burn through their entire cooperative budget.
+///
+/// In general, you should be careful using this function. Futures that never yield and perform
+/// some I/O can result in starvation of other parts of the program if not used with care.
+///
+/// # Examples
+///
+/// ```
+/// let num = futures::executor::block_on(async {
+///     tokio::task::unconstrained(async {
+///         // Some long-running operation that you don't want to be forced to yield.
+///         1 + 1
+///     })
+///     .await
+/// });
+///
+/// assert_eq!(num, 2);
+/// ```
+pub fn unconstrained<F>(future: F) -> Unconstrained<F> {
+    Unconstrained { inner: future }
+}
+
+impl<F: Future> Future for Unconstrained<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<F::Output> {
+        coop::with_unconstrained(|| self.project().inner.poll(cx))
+    }
+}